@@ -7,42 +7,152 @@ use std::{
 use anyhow::{anyhow, Context};
 use tracing::{debug, instrument, Span};
 
+/// Operations needed by [`Repo`] to inspect and manipulate a git repository.
+///
+/// This is implemented both by a process-spawning backend (the historical
+/// behavior, shelling out to the `git` binary) and by a libgit2-backed
+/// backend that avoids spawning a process for every query.
+trait GitBackend {
+    fn current_branch(&self) -> anyhow::Result<String>;
+    fn nth_commit(&self, nth: usize) -> anyhow::Result<String>;
+    fn nth_commit_at_path(&self, nth: usize, path: &Path) -> anyhow::Result<String>;
+    /// Lists the files edited by `commit`. For merge commits, diffs
+    /// against the first parent rather than assuming a linear history.
+    fn edited_files_in_commit(&self, commit: &str) -> anyhow::Result<Vec<PathBuf>>;
+    /// Returns whether `commit` has more than one parent.
+    fn is_merge_commit(&self, commit: &str) -> anyhow::Result<bool>;
+    fn checkout(&self, object: &str) -> anyhow::Result<()>;
+    fn current_commit_message(&self) -> anyhow::Result<String>;
+    fn dirty_files(&self) -> anyhow::Result<Vec<DirtyFile>>;
+    fn verify_commit_signature(
+        &self,
+        commit: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus>;
+    fn verify_tag_signature(
+        &self,
+        tag: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus>;
+    fn commit_signed(&self, message: &str) -> anyhow::Result<()>;
+    fn tag_signed(&self, name: &str, message: &str) -> anyhow::Result<()>;
+    fn fetch(&self, remote: &str, refspec: &str) -> anyhow::Result<()>;
+    fn push(&self, remote: &str, refspec: &str) -> anyhow::Result<()>;
+    fn create_tag(&self, name: &str, message: &str) -> anyhow::Result<()>;
+    /// Tag names, most recently created first.
+    fn list_tags(&self) -> anyhow::Result<Vec<String>>;
+    fn commits_since(&self, tag_or_ref: &str) -> anyhow::Result<Vec<Commit>>;
+    /// Creates a detached worktree checked out to `reference` at `path`.
+    fn add_worktree(&self, reference: &str, path: &Path) -> anyhow::Result<()>;
+    /// Removes a worktree previously created by `add_worktree`, along with
+    /// any bookkeeping refs it created.
+    fn remove_worktree(&self, path: &Path) -> anyhow::Result<()>;
+}
+
+/// A commit reachable from `HEAD`, as needed to compute the next version
+/// and generate a changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    pub hash: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// The outcome of verifying a commit's or tag's GPG/SSH signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// The signature is valid, made by the given key or email.
+    Good { signer: String },
+    /// A signature is present but does not verify.
+    Bad,
+    /// No signature is present at all.
+    Missing,
+}
+
+/// A file reported as dirty by `git status`, together with the kind of
+/// change that makes it dirty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirtyFile {
+    pub path: PathBuf,
+    pub status: FileStatus,
+}
+
+/// The kind of uncommitted change affecting a file, as reported by
+/// `git status --porcelain=v1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The change is staged in the index.
+    Staged,
+    /// The change is present in the working tree but not staged.
+    Unstaged,
+    /// The file is not tracked by git.
+    Untracked,
+    /// The file was deleted.
+    Deleted,
+}
+
+/// Selects which [`GitBackend`] a [`Repo`] is built with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GitClient {
+    /// Shell out to the `git` binary for every operation (the default).
+    #[default]
+    Cli,
+    /// Use libgit2 (via the `git2` crate) instead of spawning `git`. Signed
+    /// commits and tags still shell out to `git`, and signature
+    /// verification to `gpg`/`ssh-keygen`.
+    Libgit2,
+}
+
 /// Repository
 pub struct Repo {
-    /// Repository root directory
-    directory: PathBuf,
     current_branch: String,
+    client: GitClient,
+    backend: Box<dyn GitBackend>,
+    #[cfg(test)]
+    directory: PathBuf,
 }
 
 impl Repo {
     /// Returns an error if the directory doesn't contain any commit
     pub fn new(directory: impl AsRef<Path>) -> anyhow::Result<Self> {
-        let current_branch = Self::get_current_branch(&directory)?;
-        // TODO move this in main
-        crate::log::init();
-
-        Ok(Self {
-            directory: directory.as_ref().to_path_buf(),
-            current_branch,
-        })
+        Self::new_with_client(directory, GitClient::default())
     }
 
-    fn get_current_branch(directory: impl AsRef<Path>) -> anyhow::Result<String> {
-        let current_branch =
-            Self::git_in_dir(directory.as_ref(), &["rev-parse", "--abbrev-ref", "HEAD"])?;
-        stdout(current_branch).map_err(|e|
+    /// Same as [`Repo::new`], but lets the caller pick the [`GitBackend`]
+    /// implementation used for all subsequent operations.
+    pub fn new_with_client(
+        directory: impl AsRef<Path>,
+        client: GitClient,
+    ) -> anyhow::Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        let backend: Box<dyn GitBackend> = match client {
+            GitClient::Cli => Box::new(CliBackend {
+                directory: directory.clone(),
+            }),
+            GitClient::Libgit2 => Box::new(Libgit2Backend::open(&directory)?),
+        };
+        let current_branch = backend.current_branch().map_err(|e|
             if e.to_string().contains("fatal: ambiguous argument 'HEAD': unknown revision or path not in the working tree.") {
                 anyhow!("git repository does not contain any commit.")
             }
             else {
                 e
             }
-        )
+        )?;
+        // TODO move this in main
+        crate::log::init();
+
+        Ok(Self {
+            current_branch,
+            client,
+            backend,
+            #[cfg(test)]
+            directory,
+        })
     }
 
     pub fn checkout_head(&self) -> anyhow::Result<()> {
-        self.git(&["checkout", &self.current_branch])?;
-        Ok(())
+        self.backend.checkout(&self.current_branch)
     }
 
     #[instrument(skip(self))]
@@ -62,41 +172,73 @@ impl Repo {
         )
     )]
     fn nth_commit(&self, nth: usize) -> anyhow::Result<String> {
-        let nth = nth.to_string();
-        let output = self.git(&["--format=\"%H\"", "-n", &nth])?;
-        let commit_list = stdout(output)?;
-        let last_commit = commit_list
-            .lines()
-            .last()
-            .context("repository has no commits")?;
+        let last_commit = self.backend.nth_commit(nth)?;
         Span::current().record("nth_commit", &last_commit);
+        Ok(last_commit)
+    }
 
-        Ok(last_commit.to_string())
+    /// Returns `true` if the working tree has no staged, unstaged, untracked
+    /// or deleted changes.
+    pub fn is_clean(&self) -> anyhow::Result<bool> {
+        Ok(self.backend.dirty_files()?.is_empty())
     }
 
-    fn git_in_dir(dir: &Path, args: &[&str]) -> io::Result<Output> {
-        Command::new("git").arg("-C").arg(dir).args(args).output()
+    /// Returns the paths of all files with uncommitted changes (staged,
+    /// unstaged, untracked or deleted), relative to the repository root.
+    pub fn dirty_files(&self) -> anyhow::Result<Vec<PathBuf>> {
+        Ok(self
+            .backend
+            .dirty_files()?
+            .into_iter()
+            .map(|file| file.path)
+            .collect())
     }
 
-    /// Run a git command in the repository git directory
-    fn git(&self, args: &[&str]) -> io::Result<Output> {
-        Self::git_in_dir(&self.directory, args)
+    /// Returns an error listing the dirty files if the working tree is not
+    /// clean.
+    fn ensure_clean(&self) -> anyhow::Result<()> {
+        let dirty_files = self.backend.dirty_files()?;
+        if dirty_files.is_empty() {
+            return Ok(());
+        }
+        let dirty_files = dirty_files
+            .iter()
+            .map(|file| format!("- {} ({})", file.path.display(), file.status))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(anyhow!(
+            "cannot checkout: the working tree is not clean, \
+             please commit, stash or discard your changes first:\n{dirty_files}"
+        ))
     }
 
     /// Checkout to the latest commit. I.e. go back in history of 1 commit.
     pub fn checkout_last_commit(&self) -> anyhow::Result<()> {
+        self.ensure_clean()?;
         let previous_commit = self.previous_commit()?;
         self.checkout(&previous_commit)?;
         Ok(())
     }
 
-    /// Return the list of edited files of that commit. Absolute Path.
+    /// Return the list of edited files of the current commit. Absolute Path.
     pub fn edited_file_in_current_commit(&self) -> anyhow::Result<Vec<PathBuf>> {
-        let commit = &self.current_commit()?;
-        let output = self.git(&["diff-tree", "--no-commit-id", "--name-only", "-r", commit])?;
-        let files = stdout(output)?;
-        let files: Result<Vec<PathBuf>, io::Error> = files.lines().map(fs::canonicalize).collect();
-        Ok(files?)
+        let commit = self.current_commit()?;
+        self.edited_files_in_commit(&commit)
+    }
+
+    /// Return the list of edited files of `commit`. Absolute Path.
+    ///
+    /// Unlike a plain `diff-tree`, this correctly handles merge commits by
+    /// diffing against their first parent, so files introduced through a
+    /// merge are not missed.
+    pub fn edited_files_in_commit(&self, commit: &str) -> anyhow::Result<Vec<PathBuf>> {
+        self.backend.edited_files_in_commit(commit)
+    }
+
+    /// Returns whether `commit` is a merge commit (has more than one
+    /// parent).
+    pub fn is_merge_commit(&self, commit: &str) -> anyhow::Result<bool> {
+        self.backend.is_merge_commit(commit)
     }
 
     fn previous_commit_at_path(&self, path: &Path) -> anyhow::Result<String> {
@@ -104,16 +246,15 @@ impl Repo {
     }
 
     pub fn checkout_previous_commit_at_path(&self, path: &Path) -> anyhow::Result<()> {
+        self.ensure_clean()?;
         let commit = self.previous_commit_at_path(path)?;
         self.checkout(&commit)?;
         Ok(())
     }
 
     #[instrument(skip(self))]
-    fn checkout(&self, object: &str) -> io::Result<()> {
-        let output = self.git(&["checkout", object])?;
-        debug!("git checkout outcome: {:?}", output);
-        Ok(())
+    fn checkout(&self, object: &str) -> anyhow::Result<()> {
+        self.backend.checkout(object)
     }
 
     #[instrument(
@@ -127,32 +268,991 @@ impl Repo {
         nth: usize,
         path: impl AsRef<Path> + fmt::Debug,
     ) -> anyhow::Result<String> {
+        let last_commit = self.backend.nth_commit_at_path(nth, path.as_ref())?;
+        Span::current().record("nth_commit", &last_commit);
+        debug!("nth_commit found");
+        Ok(last_commit)
+    }
+
+    pub fn current_commit_message(&self) -> anyhow::Result<String> {
+        self.backend.current_commit_message()
+    }
+
+    /// Verifies the GPG/SSH signature of `commit`, optionally restricting
+    /// trusted keys to `keyring`.
+    pub fn verify_commit_signature(
+        &self,
+        commit: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        self.backend.verify_commit_signature(commit, keyring)
+    }
+
+    /// Verifies the GPG/SSH signature of `tag`, optionally restricting
+    /// trusted keys to `keyring`.
+    pub fn verify_tag_signature(
+        &self,
+        tag: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        self.backend.verify_tag_signature(tag, keyring)
+    }
+
+    /// Creates a commit with the given message, signed with the user's
+    /// configured signing key (`git commit -S`).
+    pub fn commit_signed(&self, message: &str) -> anyhow::Result<()> {
+        self.backend.commit_signed(message)
+    }
+
+    /// Creates an annotated, signed tag (`git tag -s`).
+    pub fn tag_signed(&self, name: &str, message: &str) -> anyhow::Result<()> {
+        self.backend.tag_signed(name, message)
+    }
+
+    /// Fetches `refspec` from `remote`.
+    pub fn fetch(&self, remote: &str, refspec: &str) -> anyhow::Result<()> {
+        self.backend.fetch(remote, refspec)
+    }
+
+    /// Pushes `refspec` to `remote`.
+    pub fn push(&self, remote: &str, refspec: &str) -> anyhow::Result<()> {
+        self.backend.push(remote, refspec)
+    }
+
+    /// Creates an annotated tag.
+    pub fn create_tag(&self, name: &str, message: &str) -> anyhow::Result<()> {
+        self.backend.create_tag(name, message)
+    }
+
+    /// Lists all tags, most recently created first.
+    pub fn list_tags(&self) -> anyhow::Result<Vec<String>> {
+        self.backend.list_tags()
+    }
+
+    /// Returns the most recently created tag whose name matches `pattern`,
+    /// a glob pattern where `*` matches any run of characters (e.g.
+    /// `mycrate-v*` to filter per-package tags in a workspace).
+    pub fn last_tag_matching(&self, pattern: &str) -> anyhow::Result<Option<String>> {
+        Ok(self
+            .list_tags()?
+            .into_iter()
+            .find(|tag| glob_match(pattern, tag)))
+    }
+
+    /// Returns the commits reachable from `HEAD` but not from
+    /// `tag_or_ref`, i.e. everything released since that point.
+    pub fn commits_since(&self, tag_or_ref: &str) -> anyhow::Result<Vec<Commit>> {
+        self.backend.commits_since(tag_or_ref)
+    }
+
+    /// Checks out `reference` into a temporary, detached worktree and runs
+    /// `f` against a [`Repo`] rooted there, leaving this repository's
+    /// working directory untouched. The worktree is removed afterwards,
+    /// even if `f` panics or returns early.
+    pub fn with_temp_worktree<T>(
+        &self,
+        reference: &str,
+        f: impl FnOnce(&Repo) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let path = unique_temp_path("release-plz-worktree");
+        self.backend.add_worktree(reference, &path)?;
+        let _guard = WorktreeGuard {
+            backend: self.backend.as_ref(),
+            worktree_path: path.clone(),
+        };
+        let worktree_repo = Repo::new_with_client(&path, self.client)?;
+        f(&worktree_repo)
+    }
+}
+
+/// Removes the temporary worktree it was created for on drop (through the
+/// same [`GitBackend`] that created it), so the worktree is cleaned up even
+/// if the caller's closure panics or returns early via `?`.
+struct WorktreeGuard<'a> {
+    backend: &'a dyn GitBackend,
+    worktree_path: PathBuf,
+}
+
+impl Drop for WorktreeGuard<'_> {
+    fn drop(&mut self) {
+        if let Err(error) = self.backend.remove_worktree(&self.worktree_path) {
+            debug!(
+                "failed to remove temporary worktree {:?}: {error}",
+                self.worktree_path
+            );
+        }
+    }
+}
+
+/// Returns a path under the system temp directory that doesn't exist yet.
+fn unique_temp_path(prefix: &str) -> PathBuf {
+    let unique = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    std::env::temp_dir().join(format!("{prefix}-{}-{unique}", std::process::id()))
+}
+
+/// Matches `candidate` against `pattern`, where `*` in `pattern` matches
+/// any run of characters (including none).
+fn glob_match(pattern: &str, candidate: &str) -> bool {
+    fn matches(pattern: &[u8], text: &[u8]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                matches(&pattern[1..], text) || (!text.is_empty() && matches(pattern, &text[1..]))
+            }
+            (Some(p), Some(t)) if p == t => matches(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+    matches(pattern.as_bytes(), candidate.as_bytes())
+}
+
+/// Backend that shells out to the `git` binary for every operation.
+struct CliBackend {
+    directory: PathBuf,
+}
+
+impl CliBackend {
+    fn git_in_dir(dir: &Path, args: &[&str]) -> io::Result<Output> {
+        Command::new("git").arg("-C").arg(dir).args(args).output()
+    }
+
+    /// Run a git command in the repository git directory
+    fn git(&self, args: &[&str]) -> io::Result<Output> {
+        Self::git_in_dir(&self.directory, args)
+    }
+}
+
+impl GitBackend for CliBackend {
+    fn current_branch(&self) -> anyhow::Result<String> {
+        let output = self.git(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+        stdout(output)
+    }
+
+    fn nth_commit(&self, nth: usize) -> anyhow::Result<String> {
         let nth = nth.to_string();
-        let path = path.as_ref().to_str().ok_or(anyhow!("invalid path"))?;
-        let output = self.git(&["log", "--format=%H", "-n", &nth, path])?;
+        let output = self.git(&["log", "--format=%H", "-n", &nth])?;
         let commit_list = stdout(output)?;
         let last_commit = commit_list
             .lines()
             .last()
             .context("repository has no commits")?;
+        Ok(last_commit.to_string())
+    }
 
-        Span::current().record("nth_commit", &last_commit);
-        debug!("nth_commit found");
+    fn nth_commit_at_path(&self, nth: usize, path: &Path) -> anyhow::Result<String> {
+        let nth = nth.to_string();
+        let path = path.to_str().ok_or(anyhow!("invalid path"))?;
+        let output = self.git(&["log", "--format=%H", "-n", &nth, path])?;
+        let commit_list = stdout(output)?;
+        let last_commit = commit_list
+            .lines()
+            .last()
+            .context("repository has no commits")?;
         Ok(last_commit.to_string())
     }
 
-    pub fn current_commit_message(&self) -> anyhow::Result<String> {
+    fn edited_files_in_commit(&self, commit: &str) -> anyhow::Result<Vec<PathBuf>> {
+        let output = if self.is_merge_commit(commit)? {
+            // `diff-tree --cc` only reports files that conflicted during the
+            // merge, so a clean merge would report no files at all. Diff
+            // against the first parent instead, which reports every file the
+            // merge actually introduced relative to the branch it merged into.
+            let first_parent = format!("{commit}^1");
+            self.git(&["diff", "--name-only", &first_parent, commit])?
+        } else {
+            self.git(&["diff-tree", "--no-commit-id", "--name-only", "-r", commit])?
+        };
+        let files = stdout(output)?;
+        let files: Result<Vec<PathBuf>, io::Error> = files
+            .lines()
+            .map(|path| fs::canonicalize(self.directory.join(path)))
+            .collect();
+        Ok(files?)
+    }
+
+    fn is_merge_commit(&self, commit: &str) -> anyhow::Result<bool> {
+        let output = self.git(&["rev-list", "--parents", "-n", "1", commit])?;
+        let line = stdout(output)?;
+        let parent_count = line.split_whitespace().count().saturating_sub(1);
+        Ok(parent_count > 1)
+    }
+
+    fn checkout(&self, object: &str) -> anyhow::Result<()> {
+        let output = self.git(&["checkout", object])?;
+        debug!("git checkout outcome: {:?}", output);
+        check_status(&output)?;
+        Ok(())
+    }
+
+    fn current_commit_message(&self) -> anyhow::Result<String> {
         let output = self.git(&["log", "-1", "--pretty=format:%s"])?;
         stdout(output)
     }
+
+    fn dirty_files(&self) -> anyhow::Result<Vec<DirtyFile>> {
+        let output = self.git(&["status", "--porcelain=v1"])?;
+        let status = stdout(output)?;
+        status.lines().map(parse_porcelain_line).collect()
+    }
+
+    fn verify_commit_signature(
+        &self,
+        commit: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        self.verify_signature("verify-commit", commit, keyring)
+    }
+
+    fn verify_tag_signature(
+        &self,
+        tag: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        self.verify_signature("verify-tag", tag, keyring)
+    }
+
+    fn commit_signed(&self, message: &str) -> anyhow::Result<()> {
+        let output = self.git(&["commit", "-S", "-m", message])?;
+        check_status(&output)
+    }
+
+    fn tag_signed(&self, name: &str, message: &str) -> anyhow::Result<()> {
+        let output = self.git(&["tag", "-s", name, "-m", message])?;
+        check_status(&output)
+    }
+
+    fn fetch(&self, remote: &str, refspec: &str) -> anyhow::Result<()> {
+        let output = self.git(&["fetch", remote, refspec])?;
+        check_status(&output)
+    }
+
+    fn push(&self, remote: &str, refspec: &str) -> anyhow::Result<()> {
+        let output = self.git(&["push", remote, refspec])?;
+        check_status(&output)
+    }
+
+    fn create_tag(&self, name: &str, message: &str) -> anyhow::Result<()> {
+        let output = self.git(&["tag", "-a", name, "-m", message])?;
+        check_status(&output)
+    }
+
+    fn list_tags(&self) -> anyhow::Result<Vec<String>> {
+        let output = self.git(&["tag", "--list", "--sort=-creatordate"])?;
+        let tags = stdout(output)?;
+        Ok(tags.lines().map(str::to_string).collect())
+    }
+
+    fn commits_since(&self, tag_or_ref: &str) -> anyhow::Result<Vec<Commit>> {
+        let range = format!("{tag_or_ref}..HEAD");
+        // Separate fields with \x01 and commits with \x02 so multi-line
+        // commit bodies don't get mistaken for commit boundaries.
+        let format_arg = "--format=%H%x01%s%x01%b%x02".to_string();
+        let output = self.git(&["log", &format_arg, &range])?;
+        let log = stdout(output)?;
+        Ok(log
+            .split('\x02')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let mut fields = entry.splitn(3, '\x01');
+                Commit {
+                    hash: fields.next().unwrap_or_default().to_string(),
+                    subject: fields.next().unwrap_or_default().to_string(),
+                    body: fields.next().unwrap_or_default().trim().to_string(),
+                }
+            })
+            .collect())
+    }
+
+    fn add_worktree(&self, reference: &str, path: &Path) -> anyhow::Result<()> {
+        let output = self.git(&[
+            "worktree",
+            "add",
+            "--detach",
+            path.to_str().context("invalid worktree path")?,
+            reference,
+        ])?;
+        check_status(&output)
+    }
+
+    fn remove_worktree(&self, path: &Path) -> anyhow::Result<()> {
+        let output = self.git(&[
+            "worktree",
+            "remove",
+            "--force",
+            path.to_str().context("invalid worktree path")?,
+        ])?;
+        check_status(&output)
+    }
 }
 
-fn stdout(output: Output) -> anyhow::Result<String> {
-    debug!("output: {:?}", output);
-    if !output.stderr.is_empty() {
-        let stderr = String::from_utf8(output.stderr)?;
+impl CliBackend {
+    /// Runs `git verify-commit`/`git verify-tag --raw` and interprets the
+    /// GnuPG status lines it writes to stderr.
+    fn verify_signature(
+        &self,
+        subcommand: &str,
+        object: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        // Resolve `object` upfront so an unknown revision is reported as an
+        // error rather than conflated with "no signature present" below:
+        // real git prints nothing at all to stdout/stderr for an unsigned
+        // object, the same as it does for some failures to resolve it.
+        let resolved = self.git(&["rev-parse", "--verify", "--quiet", &format!("{object}^{{object}}")])?;
+        if !resolved.status.success() {
+            return Err(anyhow!("unknown revision or path not in the working tree: {object}"));
+        }
+
+        let mut command = Command::new("git");
+        command
+            .arg("-C")
+            .arg(&self.directory)
+            .arg(subcommand)
+            .arg("--raw")
+            .arg(object);
+        if let Some(keyring) = keyring {
+            command.env("GNUPGHOME", keyring);
+        }
+        let output = command.output()?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if let Some(status) = parse_gpg_status(&stderr) {
+            return Ok(status);
+        }
+        // `object` is now known to exist, so any failure to find a
+        // `[GNUPG:]` status line means there's simply no signature to
+        // verify: git prints nothing at all to stdout/stderr in that case.
+        Ok(SignatureStatus::Missing)
+    }
+}
+
+impl fmt::Display for FileStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            FileStatus::Staged => "staged",
+            FileStatus::Unstaged => "unstaged",
+            FileStatus::Untracked => "untracked",
+            FileStatus::Deleted => "deleted",
+        };
+        write!(f, "{status}")
+    }
+}
+
+/// Parses a single line of `git status --porcelain=v1` output into a
+/// [`DirtyFile`].
+///
+/// Each line is a two-character `XY` status pair, a space, and a path.
+/// Renames are reported as `old -> new`; paths containing unusual
+/// characters are double-quoted and C-style escaped.
+fn parse_porcelain_line(line: &str) -> anyhow::Result<DirtyFile> {
+    if line.len() < 3 {
+        return Err(anyhow!("invalid `git status --porcelain=v1` line: {line:?}"));
+    }
+    let x = line.chars().next().context("invalid status code")?;
+    let y = line.chars().nth(1).context("invalid status code")?;
+    let rest = &line[3..];
+    // Renames/copies are reported with the destination path after " -> ".
+    let path = match rest.split_once(" -> ") {
+        Some((_old, new)) => new,
+        None => rest,
+    };
+    let path = unquote_path(path);
+
+    let status = if x == '?' && y == '?' {
+        FileStatus::Untracked
+    } else if x == 'D' || y == 'D' {
+        FileStatus::Deleted
+    } else if x != ' ' {
+        FileStatus::Staged
+    } else {
+        FileStatus::Unstaged
+    };
+
+    Ok(DirtyFile {
+        path: PathBuf::from(path),
+        status,
+    })
+}
+
+/// Strips the surrounding quotes git adds around paths with unusual
+/// characters, and undoes the handful of escape sequences it uses.
+fn unquote_path(path: &str) -> String {
+    let Some(inner) = path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) else {
+        return path.to_string();
+    };
+    // Work byte-by-byte rather than char-by-char: `\NNN` escapes each encode
+    // a single raw byte of the path's UTF-8 encoding, so a non-ASCII
+    // character is split across several consecutive escapes that must be
+    // reassembled before being interpreted as UTF-8.
+    let bytes = inner.as_bytes();
+    let mut unescaped: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            unescaped.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        match bytes.get(i + 1) {
+            Some(b't') => {
+                unescaped.push(b'\t');
+                i += 2;
+            }
+            Some(b'n') => {
+                unescaped.push(b'\n');
+                i += 2;
+            }
+            Some(b'"') => {
+                unescaped.push(b'"');
+                i += 2;
+            }
+            Some(b'\\') => {
+                unescaped.push(b'\\');
+                i += 2;
+            }
+            Some(d) if d.is_ascii_digit() => {
+                let digits = &bytes[i + 1..(i + 4).min(bytes.len())];
+                if digits.len() == 3 && digits.iter().all(u8::is_ascii_digit) {
+                    let value = digits.iter().fold(0u32, |acc, d| acc * 8 + u32::from(d - b'0'));
+                    unescaped.push(value as u8);
+                    i += 4;
+                } else {
+                    unescaped.push(b'\\');
+                    i += 1;
+                }
+            }
+            Some(&other) => {
+                unescaped.push(b'\\');
+                unescaped.push(other);
+                i += 2;
+            }
+            None => {
+                unescaped.push(b'\\');
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+/// Interprets GnuPG's machine-readable status output (as produced by `git
+/// verify-commit --raw`/`git verify-tag --raw`, or by `gpg --status-fd`).
+///
+/// Returns `None` if the output contains none of the status lines this
+/// understands, which the caller must not conflate with "no signature
+/// present" without first ruling out other failures (such as an object
+/// that doesn't resolve).
+fn parse_gpg_status(status: &str) -> Option<SignatureStatus> {
+    for line in status.lines() {
+        if let Some(signer) = line.strip_prefix("[GNUPG:] GOODSIG ") {
+            // Format is "<long keyid> <user id...>"; keep the user id.
+            let signer = signer.split_once(' ').map_or(signer, |(_, email)| email);
+            return Some(SignatureStatus::Good {
+                signer: signer.to_string(),
+            });
+        }
+        if line.starts_with("[GNUPG:] BADSIG") || line.starts_with("[GNUPG:] ERRSIG") {
+            return Some(SignatureStatus::Bad);
+        }
+    }
+    None
+}
+
+/// Backend that uses libgit2 (via the `git2` crate) instead of spawning a
+/// `git` process for every operation.
+struct Libgit2Backend {
+    repo: git2::Repository,
+}
+
+impl Libgit2Backend {
+    fn open(directory: &Path) -> anyhow::Result<Self> {
+        let repo = git2::Repository::open(directory).context("failed to open git repository")?;
+        Ok(Self { repo })
+    }
+
+    fn nth_commit_oid(&self, nth: usize) -> anyhow::Result<git2::Oid> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        // libgit2's default walk order is unspecified, not reverse
+        // chronological; without this, `nth` wouldn't agree with the CLI
+        // backend's `git log`/`rev-list` order on non-linear history.
+        revwalk.set_sorting(git2::Sort::TIME)?;
+        let oid = revwalk
+            .nth(nth - 1)
+            .context("repository has no commits")??;
+        Ok(oid)
+    }
+
+    /// Resolves a commit-ish (hash, `HEAD`, branch name, ...) to a commit.
+    fn resolve_commit(&self, spec: &str) -> anyhow::Result<git2::Commit<'_>> {
+        let object = self.repo.revparse_single(spec)?;
+        let commit = object.peel_to_commit()?;
+        Ok(commit)
+    }
+
+    /// Credential resolution for `fetch`/`push`. Unlike `CliBackend`, which
+    /// gets SSH agent and credential-helper support for free from the `git`
+    /// binary, libgit2 has no credential resolution of its own: try the SSH
+    /// agent for SSH remotes, then fall back to the system credential helper
+    /// (e.g. for HTTPS tokens), so this backend can reach non-anonymous
+    /// remotes too.
+    fn remote_callbacks(&self) -> git2::RemoteCallbacks<'_> {
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(|url, username_from_url, allowed_types| {
+            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+                if let Some(username) = username_from_url {
+                    if let Ok(cred) = git2::Cred::ssh_key_from_agent(username) {
+                        return Ok(cred);
+                    }
+                }
+            }
+            let config = self.repo.config()?;
+            git2::Cred::credential_helper(&config, url, username_from_url)
+        });
+        callbacks
+    }
+}
+
+impl GitBackend for Libgit2Backend {
+    fn current_branch(&self) -> anyhow::Result<String> {
+        let head = match self.repo.head() {
+            Ok(head) => head,
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+                return Err(anyhow!("git repository does not contain any commit."))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let name = head.shorthand().context("HEAD is not a valid UTF-8 name")?;
+        Ok(name.to_string())
+    }
+
+    fn nth_commit(&self, nth: usize) -> anyhow::Result<String> {
+        let oid = self.nth_commit_oid(nth)?;
+        Ok(oid.to_string())
+    }
+
+    fn nth_commit_at_path(&self, nth: usize, path: &Path) -> anyhow::Result<String> {
+        // Pathspecs are matched against repository-relative paths, while
+        // callers (like `CliBackend`) accept absolute paths too.
+        let path = self
+            .repo
+            .workdir()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        // See the comment in `nth_commit_oid`: without an explicit sort,
+        // libgit2's walk order is arbitrary.
+        revwalk.set_sorting(git2::Sort::TIME)?;
+        let mut matches = 0;
+        for oid in revwalk {
+            let oid = oid?;
+            let commit = self.repo.find_commit(oid)?;
+            if commit_touches_path(&self.repo, &commit, path)? {
+                matches += 1;
+                if matches == nth {
+                    return Ok(oid.to_string());
+                }
+            }
+        }
+        Err(anyhow!("repository has no commits")).context("repository has no commits")
+    }
+
+    fn edited_files_in_commit(&self, commit: &str) -> anyhow::Result<Vec<PathBuf>> {
+        let commit = self.resolve_commit(commit)?;
+        let tree = commit.tree()?;
+        // For a merge commit, a plain diff against its tree has no useful
+        // parent to compare with; diff against the first parent instead,
+        // mirroring `CliBackend`'s `git diff <commit>^1 <commit>`.
+        let parent_tree = commit.parent(0).ok().map(|parent| parent.tree()).transpose()?;
+        let diff = self
+            .repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        let mut files = Vec::new();
+        let root = self.repo.workdir().unwrap_or_else(|| self.repo.path());
+        diff.foreach(
+            &mut |delta, _| {
+                if let Some(path) = delta.new_file().path() {
+                    files.push(root.join(path));
+                }
+                true
+            },
+            None,
+            None,
+            None,
+        )?;
+        Ok(files)
+    }
+
+    fn is_merge_commit(&self, commit: &str) -> anyhow::Result<bool> {
+        let commit = self.resolve_commit(commit)?;
+        Ok(commit.parent_count() > 1)
+    }
+
+    fn checkout(&self, object: &str) -> anyhow::Result<()> {
+        let (object, reference) = self.repo.revparse_ext(object)?;
+        self.repo.checkout_tree(&object, None)?;
+        match reference {
+            Some(reference) => self.repo.set_head(
+                reference
+                    .name()
+                    .context("reference has no valid UTF-8 name")?,
+            )?,
+            None => self.repo.set_head_detached(object.id())?,
+        }
+        Ok(())
+    }
+
+    fn current_commit_message(&self) -> anyhow::Result<String> {
+        let oid = self.nth_commit_oid(1)?;
+        let commit = self.repo.find_commit(oid)?;
+        let summary = commit.summary().context("commit message is not valid UTF-8")?;
+        Ok(summary.to_string())
+    }
+
+    fn dirty_files(&self) -> anyhow::Result<Vec<DirtyFile>> {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true);
+        let statuses = self.repo.statuses(Some(&mut opts))?;
+        statuses
+            .iter()
+            .map(|entry| {
+                let path = entry.path().context("path is not valid UTF-8")?;
+                let flags = entry.status();
+                let status = if flags.contains(git2::Status::WT_NEW) {
+                    FileStatus::Untracked
+                } else if flags.contains(git2::Status::WT_DELETED)
+                    || flags.contains(git2::Status::INDEX_DELETED)
+                {
+                    FileStatus::Deleted
+                } else if flags.intersects(
+                    git2::Status::INDEX_NEW
+                        | git2::Status::INDEX_MODIFIED
+                        | git2::Status::INDEX_RENAMED
+                        | git2::Status::INDEX_TYPECHANGE,
+                ) {
+                    FileStatus::Staged
+                } else {
+                    FileStatus::Unstaged
+                };
+                Ok(DirtyFile {
+                    // Relative to the repository root, matching CliBackend.
+                    path: PathBuf::from(path),
+                    status,
+                })
+            })
+            .collect()
+    }
+
+    fn verify_commit_signature(
+        &self,
+        commit: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        let oid = git2::Oid::from_str(commit).context("invalid commit id")?;
+        self.verify_signature(oid, keyring)
+    }
+
+    fn verify_tag_signature(
+        &self,
+        tag: &str,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        let reference = self.repo.find_reference(&format!("refs/tags/{tag}"))?;
+        let oid = reference.target().context("tag has no direct target")?;
+        self.verify_signature(oid, keyring)
+    }
+
+    fn commit_signed(&self, message: &str) -> anyhow::Result<()> {
+        // libgit2 has no GPG signing support of its own; shell out for the
+        // one operation that fundamentally needs an external `git`/`gpg`.
+        let directory = self.repo.workdir().unwrap_or_else(|| self.repo.path());
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(directory)
+            .args(["commit", "-S", "-m", message])
+            .output()?;
+        check_status(&output)
+    }
+
+    fn tag_signed(&self, name: &str, message: &str) -> anyhow::Result<()> {
+        let directory = self.repo.workdir().unwrap_or_else(|| self.repo.path());
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(directory)
+            .args(["tag", "-s", name, "-m", message])
+            .output()?;
+        check_status(&output)
+    }
+
+    fn fetch(&self, remote: &str, refspec: &str) -> anyhow::Result<()> {
+        let mut remote = self
+            .repo
+            .find_remote(remote)
+            .or_else(|_| self.repo.remote_anonymous(remote))?;
+        let mut options = git2::FetchOptions::new();
+        options.remote_callbacks(self.remote_callbacks());
+        remote.fetch(&[refspec], Some(&mut options), None)?;
+        Ok(())
+    }
+
+    fn push(&self, remote: &str, refspec: &str) -> anyhow::Result<()> {
+        let mut remote = self.repo.find_remote(remote)?;
+        let mut options = git2::PushOptions::new();
+        options.remote_callbacks(self.remote_callbacks());
+        remote.push(&[refspec], Some(&mut options))?;
+        Ok(())
+    }
+
+    fn create_tag(&self, name: &str, message: &str) -> anyhow::Result<()> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        let signature = self.repo.signature()?;
+        self.repo
+            .tag(name, head.as_object(), &signature, message, false)?;
+        Ok(())
+    }
+
+    fn list_tags(&self) -> anyhow::Result<Vec<String>> {
+        let mut tags: Vec<(String, i64)> = Vec::new();
+        self.repo.tag_foreach(|oid, name| {
+            let name = String::from_utf8_lossy(name)
+                .trim_start_matches("refs/tags/")
+                .to_string();
+            let time = self
+                .repo
+                .find_tag(oid)
+                .map(|tag| tag.tagger().map(|sig| sig.when().seconds()).unwrap_or(0))
+                .or_else(|_| self.repo.find_commit(oid).map(|commit| commit.time().seconds()))
+                .unwrap_or(0);
+            tags.push((name, time));
+            true
+        })?;
+        tags.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(tags.into_iter().map(|(name, _)| name).collect())
+    }
+
+    fn commits_since(&self, tag_or_ref: &str) -> anyhow::Result<Vec<Commit>> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        // See the comment in `nth_commit_oid`: without an explicit sort,
+        // libgit2's walk order is arbitrary, which would make the generated
+        // changelog list commits in a different order than `CliBackend`.
+        revwalk.set_sorting(git2::Sort::TIME)?;
+        let (object, _) = self.repo.revparse_ext(tag_or_ref)?;
+        revwalk.hide(object.id())?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let oid = oid?;
+            let commit = self.repo.find_commit(oid)?;
+            commits.push(Commit {
+                hash: oid.to_string(),
+                subject: commit.summary().unwrap_or_default().to_string(),
+                body: commit.body().unwrap_or_default().to_string(),
+            });
+        }
+        Ok(commits)
+    }
+
+    fn add_worktree(&self, reference: &str, path: &Path) -> anyhow::Result<()> {
+        let commit = self.repo.revparse_single(reference)?.peel_to_commit()?;
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .context("invalid worktree path")?;
+        // libgit2 worktrees are always checked out to a branch; point a
+        // throwaway one at `reference` to emulate a detached checkout.
+        // `remove_worktree` deletes this branch again once the worktree is
+        // torn down.
+        let branch_name = worktree_branch_name(name);
+        let branch = self
+            .repo
+            .branch(&branch_name, &commit, true)?
+            .into_reference();
+        let mut options = git2::WorktreeAddOptions::new();
+        options.reference(Some(&branch));
+        self.repo.worktree(name, path, Some(&options))?;
+        Ok(())
+    }
+
+    fn remove_worktree(&self, path: &Path) -> anyhow::Result<()> {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .context("invalid worktree path")?;
+        if let Ok(worktree) = self.repo.find_worktree(name) {
+            let mut opts = git2::WorktreePruneOptions::new();
+            // `valid(true)` is required to prune a worktree whose directory
+            // still exists (the normal case right after the caller's closure
+            // returns) — without it libgit2 refuses with "not pruning valid
+            // working tree".
+            opts.working_tree(true).valid(true);
+            worktree.prune(Some(&mut opts))?;
+        }
+        if let Ok(mut branch) = self
+            .repo
+            .find_branch(&worktree_branch_name(name), git2::BranchType::Local)
+        {
+            branch.delete()?;
+        }
+        Ok(())
+    }
+}
+
+/// Name of the throwaway branch `Libgit2Backend::add_worktree` creates to
+/// check out a worktree at `worktree_name`.
+fn worktree_branch_name(worktree_name: &str) -> String {
+    format!("{worktree_name}-detached")
+}
+
+impl Libgit2Backend {
+    /// Extracts the raw signature and signed payload of `oid` via libgit2,
+    /// then verifies them (libgit2 itself has no GPG/SSH verification
+    /// support). Dispatches to `gpg` or `ssh-keygen -Y verify` depending on
+    /// the signature's armor header, so SSH-signed commits/tags (`gpg.format
+    /// = ssh`) are handled, not just GPG ones.
+    fn verify_signature(
+        &self,
+        oid: git2::Oid,
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        let (signature, signed_data) = match self.repo.extract_signature(&oid, None) {
+            Ok(parts) => parts,
+            Err(_) => return Ok(SignatureStatus::Missing),
+        };
+        if signature.as_ref().starts_with(b"-----BEGIN SSH SIGNATURE-----") {
+            self.verify_ssh_signature(signature.as_ref(), signed_data.as_ref(), keyring)
+        } else {
+            self.verify_gpg_signature(signature.as_ref(), signed_data.as_ref(), keyring)
+        }
+    }
+
+    fn verify_gpg_signature(
+        &self,
+        signature: &[u8],
+        signed_data: &[u8],
+        keyring: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        let sig_path = write_temp_file("release-plz-sig", signature)?;
+        let data_path = write_temp_file("release-plz-data", signed_data)?;
+
+        let mut command = Command::new("gpg");
+        command
+            .arg("--status-fd")
+            .arg("1")
+            .arg("--verify")
+            .arg(&sig_path)
+            .arg(&data_path);
+        if let Some(keyring) = keyring {
+            command.env("GNUPGHOME", keyring);
+        }
+        let output = command.output();
+
+        let _ = fs::remove_file(&sig_path);
+        let _ = fs::remove_file(&data_path);
+
+        let output = output?;
+        parse_gpg_status(&String::from_utf8_lossy(&output.stdout)).ok_or_else(|| {
+            anyhow!(
+                "could not interpret gpg verification output: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )
+        })
+    }
+
+    /// Verifies an `ssh-keygen`-format signature via `ssh-keygen -Y verify`.
+    /// `allowed_signers` must point at an SSH allowed-signers file (see
+    /// git's `gpg.ssh.allowedSignersFile`); there is no libgit2 equivalent
+    /// of a GPG keyring for SSH signatures.
+    fn verify_ssh_signature(
+        &self,
+        signature: &[u8],
+        signed_data: &[u8],
+        allowed_signers: Option<&Path>,
+    ) -> anyhow::Result<SignatureStatus> {
+        let allowed_signers = allowed_signers.context(
+            "verifying an SSH signature requires an allowed-signers file \
+             (see git's `gpg.ssh.allowedSignersFile`)",
+        )?;
+        let sig_path = write_temp_file("release-plz-sig", signature)?;
+        let data_path = write_temp_file("release-plz-data", signed_data)?;
+
+        let result = (|| -> io::Result<Output> {
+            let message = fs::File::open(&data_path)?;
+            Command::new("ssh-keygen")
+                .args(["-Y", "verify", "-f"])
+                .arg(allowed_signers)
+                .args(["-I", "git", "-n", "git", "-s"])
+                .arg(&sig_path)
+                .stdin(message)
+                .output()
+        })();
+
+        let _ = fs::remove_file(&sig_path);
+        let _ = fs::remove_file(&data_path);
+
+        let output = result?;
+        Ok(parse_ssh_verify_output(&String::from_utf8_lossy(
+            &output.stderr,
+        )))
+    }
+}
+
+/// Interprets `ssh-keygen -Y verify`'s stderr output, e.g. `Good "git"
+/// signature for jane@example.com with ED25519 key SHA256:...`.
+fn parse_ssh_verify_output(stderr: &str) -> SignatureStatus {
+    match stderr
+        .lines()
+        .find_map(|line| line.strip_prefix("Good \"git\" signature for "))
+    {
+        Some(rest) => SignatureStatus::Good {
+            signer: rest.split(" with ").next().unwrap_or(rest).trim().to_string(),
+        },
+        None => SignatureStatus::Bad,
+    }
+}
+
+/// Writes `contents` to a uniquely-named file in the system temp directory
+/// and returns its path.
+fn write_temp_file(prefix: &str, contents: &[u8]) -> anyhow::Result<PathBuf> {
+    let unique = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!("{prefix}-{}-{unique}", std::process::id()));
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Returns whether `commit` changed `path` relative to its first parent
+/// (or relative to an empty tree, if it has no parent).
+fn commit_touches_path(
+    repo: &git2::Repository,
+    commit: &git2::Commit,
+    path: &Path,
+) -> anyhow::Result<bool> {
+    let tree = commit.tree()?;
+    let parent_tree = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+    let mut diff_opts = git2::DiffOptions::new();
+    diff_opts.pathspec(path);
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
+    Ok(diff.deltas().next().is_some())
+}
+
+fn check_status(output: &Output) -> anyhow::Result<()> {
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
         return Err(anyhow!(stderr));
     }
+    Ok(())
+}
+
+fn stdout(output: Output) -> anyhow::Result<String> {
+    debug!("output: {:?}", output);
+    check_status(&output)?;
     let stdout = String::from_utf8(output.stdout)?;
     Ok(stdout)
 }
@@ -163,7 +1263,7 @@ mod tests {
 
     use super::*;
 
-    impl Repo {
+    impl CliBackend {
         fn git_add(&self) {
             self.git(&["add", "."]).unwrap();
         }
@@ -176,28 +1276,63 @@ mod tests {
             self.git_add();
             self.git_commit(message);
         }
+    }
+
+    impl Repo {
+        fn git_add_and_commit(&self, message: &str) {
+            let backend = CliBackend {
+                directory: self.directory.clone(),
+            };
+            backend.git_add_and_commit(message);
+        }
 
         fn init(directory: impl AsRef<Path>) -> Self {
-            Self::git_in_dir(directory.as_ref(), &["init"]).unwrap();
+            Self::init_with_client(directory, GitClient::Cli)
+        }
+
+        /// Same as [`Repo::init`], but lets the caller pick the backend under
+        /// test.
+        fn init_with_client(directory: impl AsRef<Path>, client: GitClient) -> Self {
+            CliBackend::git_in_dir(directory.as_ref(), &["init"]).unwrap();
             fs::write(directory.as_ref().join("README.md"), "# my awesome project").unwrap();
-            Self::git_in_dir(directory.as_ref(), &["add", "."]).unwrap();
-            Self::git_in_dir(directory.as_ref(), &["commit", "-m", "add README"]).unwrap();
-            Self::new(directory).unwrap()
+            CliBackend::git_in_dir(directory.as_ref(), &["add", "."]).unwrap();
+            CliBackend::git_in_dir(directory.as_ref(), &["commit", "-m", "add README"]).unwrap();
+            Self::new_with_client(directory, client).unwrap()
         }
     }
 
-    #[test]
-    fn inexistent_previous_commit_detected() {
+    /// Returns the name of the branch checked out in `dir`.
+    fn current_branch_name(dir: &Path) -> String {
+        CliBackend::git_in_dir(dir, &["rev-parse", "--abbrev-ref", "HEAD"])
+            .map(stdout)
+            .unwrap()
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+
+    fn inexistent_previous_commit_detected_impl(client: GitClient) {
         let repository_dir = tempdir().unwrap();
-        let repo = Repo::init(&repository_dir);
+        let repo = Repo::init_with_client(&repository_dir, client);
         let file1 = repository_dir.as_ref().join("file1.txt");
         repo.checkout_previous_commit_at_path(&file1).unwrap_err();
     }
 
     #[test]
-    fn previous_commit_is_retrieved() {
+    fn inexistent_previous_commit_detected_cli() {
+        inexistent_previous_commit_detected_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn inexistent_previous_commit_detected_libgit2() {
+        inexistent_previous_commit_detected_impl(GitClient::Libgit2);
+    }
+
+    /// Exercises `nth_commit_at_path` and `checkout` together, since
+    /// checking out the previous commit at a path requires both.
+    fn previous_commit_is_retrieved_impl(client: GitClient) {
         let repository_dir = tempdir().unwrap();
-        let repo = Repo::init(&repository_dir);
+        let repo = Repo::init_with_client(&repository_dir, client);
         let file1 = repository_dir.as_ref().join("file1.txt");
         let file2 = repository_dir.as_ref().join("file2.txt");
         {
@@ -213,9 +1348,19 @@ mod tests {
     }
 
     #[test]
-    fn current_commit_is_retrieved() {
+    fn previous_commit_is_retrieved_cli() {
+        previous_commit_is_retrieved_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn previous_commit_is_retrieved_libgit2() {
+        previous_commit_is_retrieved_impl(GitClient::Libgit2);
+    }
+
+    /// Exercises `nth_commit` (via `current_commit_message`).
+    fn current_commit_is_retrieved_impl(client: GitClient) {
         let repository_dir = tempdir().unwrap();
-        let repo = Repo::init(&repository_dir);
+        let repo = Repo::init_with_client(&repository_dir, client);
         let file1 = repository_dir.as_ref().join("file1.txt");
         let commit_message = "file1 message";
         {
@@ -224,4 +1369,459 @@ mod tests {
         }
         assert_eq!(repo.current_commit_message().unwrap(), commit_message);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn current_commit_is_retrieved_cli() {
+        current_commit_is_retrieved_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn current_commit_is_retrieved_libgit2() {
+        current_commit_is_retrieved_impl(GitClient::Libgit2);
+    }
+
+    fn current_branch_is_retrieved_impl(client: GitClient) {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init_with_client(&repository_dir, client);
+        let expected = current_branch_name(repository_dir.as_ref());
+        assert_eq!(repo.current_branch, expected);
+    }
+
+    #[test]
+    fn current_branch_is_retrieved_cli() {
+        current_branch_is_retrieved_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn current_branch_is_retrieved_libgit2() {
+        current_branch_is_retrieved_impl(GitClient::Libgit2);
+    }
+
+    /// Exercises `is_merge_commit` and `edited_files_in_commit` together.
+    fn edited_files_of_clean_merge_commit_are_detected_impl(client: GitClient) {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init_with_client(&repository_dir, client);
+        let dir = repository_dir.as_ref();
+        let base_branch = current_branch_name(dir);
+
+        CliBackend::git_in_dir(dir, &["checkout", "-q", "-b", "feature"]).unwrap();
+        fs::write(dir.join("feature.txt"), b"Hello from feature!").unwrap();
+        repo.git_add_and_commit("add feature.txt");
+
+        CliBackend::git_in_dir(dir, &["checkout", "-q", &base_branch]).unwrap();
+        fs::write(dir.join("base.txt"), b"Hello from base!").unwrap();
+        repo.git_add_and_commit("add base.txt");
+
+        CliBackend::git_in_dir(dir, &["merge", "--no-ff", "-m", "merge feature", "feature"]).unwrap();
+
+        assert!(repo.is_merge_commit("HEAD").unwrap());
+        assert_eq!(
+            repo.edited_files_in_commit("HEAD").unwrap(),
+            vec![dir.join("feature.txt").canonicalize().unwrap()]
+        );
+    }
+
+    #[test]
+    fn edited_files_of_clean_merge_commit_are_detected_cli() {
+        edited_files_of_clean_merge_commit_are_detected_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn edited_files_of_clean_merge_commit_are_detected_libgit2() {
+        edited_files_of_clean_merge_commit_are_detected_impl(GitClient::Libgit2);
+    }
+
+    /// Exercises `edited_file_in_current_commit` itself (not just
+    /// `edited_files_in_commit` with an explicit commit), since it goes
+    /// through `current_commit` -> `nth_commit(1)`.
+    fn edited_file_in_current_commit_is_retrieved_impl(client: GitClient) {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init_with_client(&repository_dir, client);
+        let file1 = repository_dir.as_ref().join("file1.txt");
+        fs::write(&file1, b"Hello, file1!").unwrap();
+        repo.git_add_and_commit("add file1");
+
+        assert_eq!(
+            repo.edited_file_in_current_commit().unwrap(),
+            vec![file1.canonicalize().unwrap()]
+        );
+    }
+
+    #[test]
+    fn edited_file_in_current_commit_is_retrieved_cli() {
+        edited_file_in_current_commit_is_retrieved_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn edited_file_in_current_commit_is_retrieved_libgit2() {
+        edited_file_in_current_commit_is_retrieved_impl(GitClient::Libgit2);
+    }
+
+    #[test]
+    fn glob_match_supports_wildcards() {
+        assert!(glob_match("mycrate-v*", "mycrate-v1.0.0"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("exact", "exact"));
+        assert!(!glob_match("mycrate-v*", "othercrate-v1.0.0"));
+        assert!(!glob_match("exact", "exactly"));
+    }
+
+    /// Exercises `create_tag`, `list_tags` and `last_tag_matching` together.
+    fn last_tag_matching_filters_by_prefix_impl(client: GitClient) {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init_with_client(&repository_dir, client);
+        repo.create_tag("app-v1.0.0", "app release").unwrap();
+        repo.create_tag("lib-v1.0.0", "lib release").unwrap();
+
+        let tags = repo.list_tags().unwrap();
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&"app-v1.0.0".to_string()));
+        assert!(tags.contains(&"lib-v1.0.0".to_string()));
+
+        assert_eq!(
+            repo.last_tag_matching("app-v*").unwrap(),
+            Some("app-v1.0.0".to_string())
+        );
+        assert_eq!(repo.last_tag_matching("nonexistent-*").unwrap(), None);
+    }
+
+    #[test]
+    fn last_tag_matching_filters_by_prefix_cli() {
+        last_tag_matching_filters_by_prefix_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn last_tag_matching_filters_by_prefix_libgit2() {
+        last_tag_matching_filters_by_prefix_impl(GitClient::Libgit2);
+    }
+
+    fn commits_since_lists_commits_after_tag_impl(client: GitClient) {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init_with_client(&repository_dir, client);
+        repo.create_tag("v1.0.0", "release 1.0.0").unwrap();
+
+        fs::write(repository_dir.as_ref().join("file1.txt"), b"hi").unwrap();
+        repo.git_add_and_commit("add file1");
+        fs::write(repository_dir.as_ref().join("file2.txt"), b"hi").unwrap();
+        repo.git_add_and_commit("add file2");
+
+        let commits = repo.commits_since("v1.0.0").unwrap();
+        let subjects: Vec<_> = commits.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["add file2", "add file1"]);
+    }
+
+    #[test]
+    fn commits_since_lists_commits_after_tag_cli() {
+        commits_since_lists_commits_after_tag_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn commits_since_lists_commits_after_tag_libgit2() {
+        commits_since_lists_commits_after_tag_impl(GitClient::Libgit2);
+    }
+
+    /// Exercises `push` against a local bare "remote" repository.
+    fn push_updates_the_remote_ref_impl(client: GitClient) {
+        let remote_dir = tempdir().unwrap();
+        CliBackend::git_in_dir(remote_dir.as_ref(), &["init", "--bare"]).unwrap();
+
+        let local_dir = tempdir().unwrap();
+        let repo = Repo::init_with_client(&local_dir, client);
+        let branch = current_branch_name(local_dir.as_ref());
+        let remote_url = remote_dir.as_ref().to_str().unwrap().to_string();
+        CliBackend::git_in_dir(local_dir.as_ref(), &["remote", "add", "origin", &remote_url])
+            .unwrap();
+
+        repo.push(
+            "origin",
+            &format!("refs/heads/{branch}:refs/heads/{branch}"),
+        )
+        .unwrap();
+
+        let remote_log =
+            CliBackend::git_in_dir(remote_dir.as_ref(), &["log", "-1", "--format=%s", &branch])
+                .map(stdout)
+                .unwrap()
+                .unwrap();
+        assert_eq!(remote_log.trim(), "add README");
+    }
+
+    #[test]
+    fn push_updates_the_remote_ref_cli() {
+        push_updates_the_remote_ref_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn push_updates_the_remote_ref_libgit2() {
+        push_updates_the_remote_ref_impl(GitClient::Libgit2);
+    }
+
+    /// Exercises `fetch` by pulling a commit pushed by another clone of the
+    /// same bare "remote" repository.
+    fn fetch_retrieves_new_remote_commits_impl(client: GitClient) {
+        let remote_dir = tempdir().unwrap();
+        CliBackend::git_in_dir(remote_dir.as_ref(), &["init", "--bare"]).unwrap();
+        let remote_url = remote_dir.as_ref().to_str().unwrap().to_string();
+
+        let seed_dir = tempdir().unwrap();
+        let seed_repo = Repo::init_with_client(&seed_dir, GitClient::Cli);
+        let branch = current_branch_name(seed_dir.as_ref());
+        CliBackend::git_in_dir(seed_dir.as_ref(), &["remote", "add", "origin", &remote_url])
+            .unwrap();
+        seed_repo
+            .push(
+                "origin",
+                &format!("refs/heads/{branch}:refs/heads/{branch}"),
+            )
+            .unwrap();
+
+        let local_dir = tempdir().unwrap();
+        CliBackend::git_in_dir(local_dir.as_ref(), &["clone", "-q", &remote_url, "."]).unwrap();
+        let repo = Repo::new_with_client(&local_dir, client).unwrap();
+
+        fs::write(seed_dir.as_ref().join("file1.txt"), b"hi").unwrap();
+        seed_repo.git_add_and_commit("add file1");
+        seed_repo
+            .push(
+                "origin",
+                &format!("refs/heads/{branch}:refs/heads/{branch}"),
+            )
+            .unwrap();
+
+        repo.fetch(
+            "origin",
+            &format!("refs/heads/{branch}:refs/remotes/origin/{branch}"),
+        )
+        .unwrap();
+
+        let fetched_log = CliBackend::git_in_dir(
+            local_dir.as_ref(),
+            &[
+                "log",
+                "-1",
+                "--format=%s",
+                &format!("refs/remotes/origin/{branch}"),
+            ],
+        )
+        .map(stdout)
+        .unwrap()
+        .unwrap();
+        assert_eq!(fetched_log.trim(), "add file1");
+    }
+
+    #[test]
+    fn fetch_retrieves_new_remote_commits_cli() {
+        fetch_retrieves_new_remote_commits_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn fetch_retrieves_new_remote_commits_libgit2() {
+        fetch_retrieves_new_remote_commits_impl(GitClient::Libgit2);
+    }
+
+    /// Exercises the worktree itself, that it's cleaned up once the closure
+    /// returns, and (for `Libgit2Backend`) that the throwaway bookkeeping
+    /// branch it creates is cleaned up alongside it.
+    fn with_temp_worktree_impl(client: GitClient) {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init_with_client(&repository_dir, client);
+        let branch = current_branch_name(repository_dir.as_ref());
+
+        let mut worktree_path = None;
+        repo.with_temp_worktree(&branch, |worktree_repo| {
+            worktree_path = Some(worktree_repo.directory.clone());
+            assert!(worktree_repo.directory.join("README.md").exists());
+            assert_eq!(
+                worktree_repo.current_commit_message().unwrap(),
+                "add README"
+            );
+            Ok(())
+        })
+        .unwrap();
+
+        let worktree_path = worktree_path.unwrap();
+        assert!(
+            !worktree_path.exists(),
+            "worktree directory should be removed once the closure returns"
+        );
+
+        if client == GitClient::Libgit2 {
+            let name = worktree_path.file_name().and_then(|n| n.to_str()).unwrap();
+            let output = CliBackend::git_in_dir(
+                repository_dir.as_ref(),
+                &[
+                    "rev-parse",
+                    "--verify",
+                    &format!("refs/heads/{}", worktree_branch_name(name)),
+                ],
+            )
+            .unwrap();
+            assert!(
+                !output.status.success(),
+                "throwaway worktree branch should be deleted"
+            );
+        }
+    }
+
+    #[test]
+    fn with_temp_worktree_cli() {
+        with_temp_worktree_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn with_temp_worktree_libgit2() {
+        with_temp_worktree_impl(GitClient::Libgit2);
+    }
+
+    #[test]
+    fn with_temp_worktree_cleans_up_on_panic() {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init(&repository_dir);
+        let branch = current_branch_name(repository_dir.as_ref());
+
+        let mut worktree_path = None;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            repo.with_temp_worktree(&branch, |worktree_repo| -> anyhow::Result<()> {
+                worktree_path = Some(worktree_repo.directory.clone());
+                panic!("boom");
+            })
+        }));
+        assert!(result.is_err());
+
+        let worktree_path = worktree_path.unwrap();
+        assert!(
+            !worktree_path.exists(),
+            "worktree directory should be removed even if the closure panics"
+        );
+    }
+
+    #[test]
+    fn porcelain_line_is_parsed() {
+        let staged = parse_porcelain_line("M  staged.txt").unwrap();
+        assert_eq!(staged.path, PathBuf::from("staged.txt"));
+        assert_eq!(staged.status, FileStatus::Staged);
+
+        let unstaged = parse_porcelain_line(" M unstaged.txt").unwrap();
+        assert_eq!(unstaged.path, PathBuf::from("unstaged.txt"));
+        assert_eq!(unstaged.status, FileStatus::Unstaged);
+
+        let untracked = parse_porcelain_line("?? untracked.txt").unwrap();
+        assert_eq!(untracked.path, PathBuf::from("untracked.txt"));
+        assert_eq!(untracked.status, FileStatus::Untracked);
+
+        let deleted = parse_porcelain_line(" D deleted.txt").unwrap();
+        assert_eq!(deleted.path, PathBuf::from("deleted.txt"));
+        assert_eq!(deleted.status, FileStatus::Deleted);
+
+        let renamed = parse_porcelain_line("R  old.txt -> new.txt").unwrap();
+        assert_eq!(renamed.path, PathBuf::from("new.txt"));
+        assert_eq!(renamed.status, FileStatus::Staged);
+
+        let quoted = parse_porcelain_line("?? \"quoted\\tfile.txt\"").unwrap();
+        assert_eq!(quoted.path, PathBuf::from("quoted\tfile.txt"));
+
+        // `core.quotePath=true` (the default) quotes non-ASCII bytes as
+        // octal escapes, one per raw UTF-8 byte.
+        let non_ascii = parse_porcelain_line("?? \"h\\303\\251llo.txt\"").unwrap();
+        assert_eq!(non_ascii.path, PathBuf::from("héllo.txt"));
+    }
+
+    fn dirty_files_are_detected_relative_to_root_impl(client: GitClient) {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init_with_client(&repository_dir, client);
+        assert!(repo.is_clean().unwrap());
+
+        fs::write(repository_dir.as_ref().join("untracked.txt"), b"hello").unwrap();
+
+        assert!(!repo.is_clean().unwrap());
+        assert_eq!(
+            repo.dirty_files().unwrap(),
+            vec![PathBuf::from("untracked.txt")]
+        );
+    }
+
+    #[test]
+    fn dirty_files_are_detected_relative_to_root_cli() {
+        dirty_files_are_detected_relative_to_root_impl(GitClient::Cli);
+    }
+
+    #[test]
+    fn dirty_files_are_detected_relative_to_root_libgit2() {
+        dirty_files_are_detected_relative_to_root_impl(GitClient::Libgit2);
+    }
+
+    #[test]
+    fn dirty_files_with_non_ascii_names_are_detected() {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init(&repository_dir);
+
+        fs::write(repository_dir.as_ref().join("héllo.txt"), b"hello").unwrap();
+
+        assert_eq!(
+            repo.dirty_files().unwrap(),
+            vec![PathBuf::from("héllo.txt")]
+        );
+    }
+
+    #[test]
+    fn gpg_good_signature_is_parsed() {
+        let status = parse_gpg_status(
+            "[GNUPG:] NEWSIG\n\
+             [GNUPG:] GOODSIG ABCDEF1234567890 Jane Doe <jane@example.com>\n\
+             [GNUPG:] VALIDSIG 0123...",
+        );
+        assert_eq!(
+            status,
+            Some(SignatureStatus::Good {
+                signer: "Jane Doe <jane@example.com>".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn gpg_bad_signature_is_parsed() {
+        let status = parse_gpg_status("[GNUPG:] BADSIG ABCDEF1234567890 Jane Doe <jane@example.com>");
+        assert_eq!(status, Some(SignatureStatus::Bad));
+    }
+
+    #[test]
+    fn gpg_status_without_known_markers_is_unrecognized() {
+        assert_eq!(parse_gpg_status("fatal: no such object"), None);
+    }
+
+    #[test]
+    fn unsigned_commit_signature_is_missing() {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init(&repository_dir);
+        assert_eq!(
+            repo.verify_commit_signature("HEAD", None).unwrap(),
+            SignatureStatus::Missing
+        );
+    }
+
+    #[test]
+    fn unresolvable_commit_signature_verification_errors() {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init(&repository_dir);
+        repo.verify_commit_signature("deadbeef", None).unwrap_err();
+    }
+
+    #[test]
+    fn ssh_good_signature_is_parsed() {
+        let status = parse_ssh_verify_output(
+            "Good \"git\" signature for jane@example.com with ED25519 key SHA256:abc\n",
+        );
+        assert_eq!(
+            status,
+            SignatureStatus::Good {
+                signer: "jane@example.com".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn ssh_bad_signature_is_parsed() {
+        let status = parse_ssh_verify_output("Could not verify signature.\n");
+        assert_eq!(status, SignatureStatus::Bad);
+    }
+}